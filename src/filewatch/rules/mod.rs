@@ -2,6 +2,7 @@
 // global variables
 use crate::DRY_RUN;
 use crate::VERBOSE;
+use crate::WATCH;
 
 // serde compatibility for regex
 mod regex;
@@ -9,13 +10,40 @@ use self::regex::Regex;
 
 use anyhow::{anyhow, Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
-use log::{error, info};
+use lazy_static::lazy_static;
+use log::{error, info, warn};
 use rayon::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fs;
+use std::io::Write;
+use std::os::unix::fs::FileTypeExt;
 use std::os::unix::fs::PermissionsExt;
+use std::sync::{Mutex, MutexGuard};
 use walkdir::WalkDir;
 
+lazy_static! {
+    // execute() drives every matched path through rayon's par_iter(), so OnConflict::Rename's
+    // check-then-act candidate-name loop in resolve_conflict is otherwise racy: two threads can
+    // see the same candidate as free and both write to it. This serializes conflict resolution
+    // and the write that follows it for the rename mode, so only one thread reserves and uses a
+    // given candidate name at a time.
+    static ref RENAME_CONFLICT_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// ``lock_for_rename_conflict`` returns a held guard when `on_conflict` is `Rename`, `None`
+/// otherwise; holding the returned guard for the duration of conflict resolution plus the
+/// subsequent write is what makes the pair race-free.
+fn lock_for_rename_conflict(on_conflict: Option<&OnConflict>) -> Option<MutexGuard<'static, ()>> {
+    match on_conflict {
+        Some(OnConflict::Rename) => Some(
+            RENAME_CONFLICT_LOCK
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()),
+        ),
+        _ => None,
+    }
+}
+
 /// ``RuleActionType`` is an enum that defines multiple actions that can be performed on a file
 #[derive(Hash, PartialEq, Eq, Debug, Deserialize)]
 pub enum RuleActionType {
@@ -37,6 +65,143 @@ pub enum RuleActionType {
     /// ``Chmod`` changes the permissions of a file
     #[serde(alias = "chmod")]
     Chmod,
+    /// ``Archive`` zips every matched path into a single destination archive
+    #[serde(alias = "archive")]
+    Archive,
+}
+
+impl RuleActionType {
+    /// ``planned_kind`` is the ``PlannedActionKind`` a cache hit should be recorded
+    /// under, without actually running the action's method to find out
+    fn planned_kind(&self) -> PlannedActionKind {
+        match self {
+            RuleActionType::Move => PlannedActionKind::Move,
+            RuleActionType::Rename => PlannedActionKind::Rename,
+            RuleActionType::Delete => PlannedActionKind::Delete,
+            RuleActionType::Copy => PlannedActionKind::Copy,
+            RuleActionType::Link => PlannedActionKind::Link,
+            RuleActionType::Chmod => PlannedActionKind::Chmod,
+            RuleActionType::Archive => PlannedActionKind::Archive,
+        }
+    }
+}
+
+/// ``PlannedActionKind`` mirrors ``RuleActionType`` for the purposes of a dry-run plan record
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlannedActionKind {
+    Rename,
+    Move,
+    Delete,
+    Copy,
+    Link,
+    Chmod,
+    Archive,
+}
+
+/// ``PlannedAction`` is what one of ``RuleAction``'s per-path methods actually did (or, in
+/// `DRY_RUN`, would have done) to a single matched path - the record a dry-run plan is built from
+#[derive(Debug, Serialize)]
+pub struct PlannedAction {
+    pub action: PlannedActionKind,
+    pub source: String,
+    pub destination: Option<String>,
+    pub permissions: Option<u32>,
+    pub skipped: bool,
+}
+
+// errno for "cross-device link", returned by rename(2) when src and dst are on different filesystems
+const EXDEV: i32 = 18;
+
+/// ``resolve_destination`` builds the final path for a move/copy/link, the
+/// way `mv` does: if `destination` is an existing directory, the source's
+/// filename is pushed into it; otherwise `destination` is used as-is, which
+/// lets a rule rename-on-move in a single action.
+fn resolve_destination(path: &str, destination: &str) -> Result<String> {
+    if std::path::Path::new(destination).is_dir() {
+        let filename = path
+            .split('/')
+            .last()
+            .with_context(|| format!("Failed to parse filename from path: {:?}", path))?;
+
+        return Ok(format!("{}/{}", destination, filename));
+    }
+
+    Ok(destination.to_string())
+}
+
+/// ``OnConflict`` controls what a move/copy/link does when its resolved
+/// destination already exists.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OnConflict {
+    /// overwrite the existing file (the default)
+    Overwrite,
+    /// leave the existing file alone and skip the action
+    Skip,
+    /// insert an incrementing `(n)` suffix before the extension until a free name is found
+    Rename,
+}
+
+/// ``resolve_conflict`` applies `on_conflict` to a destination already built
+/// by `resolve_destination`. Returns `None` when the action should be skipped
+/// entirely (`skip` with an existing destination); otherwise returns the path
+/// the action should actually write to.
+fn resolve_conflict(new_path: String, on_conflict: Option<&OnConflict>) -> Option<String> {
+    if !std::path::Path::new(&new_path).exists() {
+        return Some(new_path);
+    }
+
+    match on_conflict {
+        None | Some(OnConflict::Overwrite) => Some(new_path),
+        Some(OnConflict::Skip) => None,
+        Some(OnConflict::Rename) => {
+            let path = std::path::Path::new(&new_path);
+            let parent = path
+                .parent()
+                .map_or(String::new(), |p| p.to_string_lossy().into_owned());
+            let stem = path
+                .file_stem()
+                .map_or(String::new(), |s| s.to_string_lossy().into_owned());
+            let ext = path.extension().map(|e| e.to_string_lossy().into_owned());
+
+            let mut n = 1;
+            loop {
+                let candidate_name = match &ext {
+                    Some(ext) => format!("{} ({}).{}", stem, n, ext),
+                    None => format!("{} ({})", stem, n),
+                };
+                let candidate = if parent.is_empty() {
+                    candidate_name
+                } else {
+                    format!("{}/{}", parent, candidate_name)
+                };
+
+                if !std::path::Path::new(&candidate).exists() {
+                    return Some(candidate);
+                }
+                n += 1;
+            }
+        }
+    }
+}
+
+/// ``MatchSyntax`` selects how `match_regex`/`match_pattern` is compiled into
+/// the regex ultimately used for matching.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchSyntax {
+    /// treat the pattern as a raw regex (the default)
+    Regex,
+    /// translate a shell-style glob (`*`, `**`, `?`, `[...]`, `{a,b}`) into a regex,
+    /// matching at any depth under `watch_dir` (an implicit `**/` prefix)
+    Glob,
+    /// treat the pattern as a literal directory prefix, matching it or anything under it
+    Path,
+    /// like `glob`, but anchored at the root of `watch_dir` instead of matching at any depth
+    RootGlob,
+    /// match only files directly inside the given directory - no nested subdirectories
+    RootFilesIn,
 }
 
 /// ``RuleAction`` is a struct that defines actions that can be performed on a file
@@ -48,36 +213,177 @@ pub struct RuleAction {
     watch_dir: String,
     /// ``match_regex`` is the regex to match files against
     match_regex: Option<Regex>,
+    /// ``match_syntax`` selects how `match_pattern` is compiled; `match_regex` is always a raw
+    /// regex, kept for back-compat, and is only used when `match_syntax` is unset
+    match_syntax: Option<MatchSyntax>,
+    /// ``match_pattern`` is a pattern string compiled according to `match_syntax`
+    match_pattern: Option<String>,
+    /// ``match_patterns`` are additional patterns (compiled the same way as `match_pattern`), any
+    /// one of which matches a path in addition to `match_regex`/`match_pattern`; with no
+    /// `match_syntax`, a pattern without regex metacharacters only matches a path equal to it
+    match_patterns: Option<Vec<String>>,
+    /// ``exclude_patterns`` are patterns (compiled the same way as `match_pattern`) that prune
+    /// a path - and, for directories, everything under it - out of the match
+    exclude_patterns: Option<Vec<String>>,
     /// ``rename_pattern`` is the pattern to rename files with
     rename_pattern: Option<String>,
     /// ``destination_dir`` is the directory to move files to
     destination_dir: Option<String>,
+    /// ``on_conflict`` controls what `mv`/`copy`/`link` do when their resolved
+    /// destination already exists (defaults to `overwrite`)
+    on_conflict: Option<OnConflict>,
+    /// ``cache_path`` turns on a SQLite-backed ledger of already-processed files at this
+    /// path, so a rule run on an overlapping schedule doesn't repeat work on unchanged files
+    cache_path: Option<String>,
     /// ``permissions`` is the permissions to set on a file
     permissions: Option<u32>,
+    /// ``archive_compression`` is the compression method for the `archive` action:
+    /// "stored", "deflated" (the default), or "bzip2"
+    archive_compression: Option<String>,
+    /// ``archive_level`` is the compression level for the `archive` action, passed
+    /// through to the zip writer
+    archive_level: Option<i64>,
+    /// ``link_type`` selects what kind of link the `link` action creates: "hard"
+    /// (the default) or "symbolic"/"symlink"
+    link_type: Option<String>,
 }
 
 impl RuleAction {
-    /// ``execute`` is a function that executes a rule action
-    pub fn execute(&self) -> Result<()> {
-        // parse match_regex
-        let match_regex = match self.match_regex {
-            Some(ref regex) => regex,
+    /// ``compiled_match_regex`` resolves `match_syntax`/`match_pattern` (or the
+    /// raw `match_regex`, when no syntax is given; it takes precedence over
+    /// `match_pattern` then) into the regex used to match files against
+    /// `watch_dir`. Returns `None` when none of those are usable but
+    /// `match_patterns` is set - a `RuleAction` may rely on `match_patterns`
+    /// alone - and errors otherwise. Once `match_syntax` is set, `match_regex`
+    /// is not used and `match_pattern` is required.
+    fn compiled_match_regex(&self) -> Result<Option<Regex>> {
+        if self.match_syntax.is_none()
+            && (self.match_regex.is_some() || self.match_pattern.is_none())
+        {
+            return match self.match_regex {
+                Some(ref regex) => Ok(Some(regex.clone())),
+                None if self.match_patterns.is_some() => Ok(None),
+                None => Err(anyhow!(
+                    "match_regex, match_pattern/match_syntax, or match_patterns is required for rule action {:?}",
+                    self.action
+                )),
+            };
+        }
+
+        let pattern = match self.match_pattern {
+            Some(ref pattern) => pattern,
+            None if self.match_patterns.is_some() => return Ok(None),
             None => {
                 return Err(anyhow!(
-                    "match_regex is required for rule action {:?}",
+                    "match_pattern is required when match_syntax is set for rule action {:?}",
                     self.action
                 ))
             }
         };
 
-        // match paths with regex pattern
-        let paths = match_directory_listing(self.watch_dir.as_str(), match_regex)
-            .with_context(|| format!("Failed to match directory listing for rule: {:?}", self))?;
+        compile_pattern(self.match_syntax.as_ref(), pattern).map(|r| Some(Regex::from(r)))
+    }
+
+    /// ``compiled_exclude_regexes`` compiles `exclude_patterns`, using the
+    /// same `match_syntax` as `match_pattern`, into a list of regexes that
+    /// prune a path (and, for directories, everything beneath it) out of the match.
+    fn compiled_exclude_regexes(&self) -> Result<Vec<::regex::Regex>> {
+        match &self.exclude_patterns {
+            None => Ok(Vec::new()),
+            Some(patterns) => patterns
+                .iter()
+                .map(|pattern| compile_pattern(self.match_syntax.as_ref(), pattern))
+                .collect(),
+        }
+    }
+
+    /// ``compiled_pattern_set`` compiles `match_patterns`, using the same
+    /// `match_syntax` as `match_pattern`, into a `PatternSet` that matches a
+    /// path against any of them in a single pass.
+    fn compiled_pattern_set(&self) -> Result<Option<PatternSet>> {
+        match &self.match_patterns {
+            None => Ok(None),
+            Some(patterns) => PatternSet::new(self.match_syntax.as_ref(), patterns).map(Some),
+        }
+    }
+
+    /// ``cache_scope`` is the key `Cache` stores a path's fingerprint under. `RuleAction`
+    /// has no name of its own, so its action type plus its directories stand in for one.
+    fn cache_scope(&self) -> String {
+        format!(
+            "{:?}:{}:{}",
+            self.action,
+            self.watch_dir,
+            self.destination_dir.as_deref().unwrap_or("")
+        )
+    }
+
+    /// ``execute`` is a function that executes a rule action
+    pub fn execute(&self) -> Result<()> {
+        // resolve the match pattern, regardless of which syntax it was written in
+        let match_regex = self.compiled_match_regex()?;
+        let pattern_set = self.compiled_pattern_set()?;
+        let exclude_regexes = self.compiled_exclude_regexes()?;
+
+        // match paths against match_regex/match_pattern, OR'd with match_patterns when
+        // present, pruning anything exclude_patterns rules out
+        let listing = match_directory_listing(
+            self.watch_dir.as_str(),
+            |text| {
+                match_regex
+                    .as_ref()
+                    .is_some_and(|regex| regex.is_match(text))
+                    || pattern_set.as_ref().is_some_and(|set| set.is_match(text))
+            },
+            &exclude_regexes,
+        )
+        .with_context(|| format!("Failed to match directory listing for rule: {:?}", self))?;
+        let paths = listing.paths;
 
         if *VERBOSE {
             info!("matched paths: {:?}", paths);
         }
 
+        if !listing.bad_matches.is_empty() {
+            if *VERBOSE {
+                for bad_match in &listing.bad_matches {
+                    info!("skipped entry: {:?}", bad_match);
+                }
+                error!(
+                    "skipped {} unreadable or unsupported entries while walking {:?}: {:?}",
+                    listing.bad_matches.len(),
+                    self.watch_dir,
+                    listing.bad_matches
+                );
+            } else {
+                // the log crate is only initialized in verbose mode (see main.rs), so
+                // error!/info! are silent no-ops here - this summary has to be printed
+                // directly or it never reaches the user in the tool's default mode
+                eprintln!(
+                    "skipped {} unreadable or unsupported entries while walking {:?}",
+                    listing.bad_matches.len(),
+                    self.watch_dir
+                );
+            }
+        }
+
+        // archive collects every matched path into a single zip rather than acting
+        // on each one independently, so it takes over before the per-path loop below
+        if let RuleActionType::Archive = self.action {
+            let planned = self.archive(&paths)?;
+
+            // same as the per-path plan below: the manifest IS the dry-run output, and
+            // has to be printed directly rather than logged, since the log crate isn't
+            // initialized outside --verbose
+            if *DRY_RUN {
+                let plan_json = serde_json::to_string_pretty(&planned)
+                    .with_context(|| "Failed to serialize dry-run plan to JSON")?;
+                println!("{}", plan_json);
+            }
+
+            return Ok(());
+        }
+
         let mut progress_bar: Option<ProgressBar> = Option::None;
 
         if !*VERBOSE {
@@ -102,45 +408,89 @@ impl RuleAction {
             progress_bar.set_style(progress_bar_style);
         }
 
-        // iterate over matched paths
-        paths.par_iter().for_each(|path| {
-            let path_str = path.as_str();
+        // a cache_path turns on an idempotency ledger: a path whose fingerprint is already
+        // recorded under this action's scope is skipped instead of reprocessed. --watch defaults
+        // this on even without an explicit cache_path - otherwise every tick would reprocess (or
+        // fail on) paths a prior tick already moved/deleted/linked.
+        let cache = match self.cache_path.as_deref() {
+            Some(cache_path) => Some(Cache::open(cache_path)),
+            None if *WATCH => Some(Cache::open(&Cache::default_path())),
+            None => None,
+        };
+        let cache_scope = self.cache_scope();
 
-            let res = match self.action {
-                RuleActionType::Rename => self.rename(path_str),
-                RuleActionType::Move => self.mv(path_str),
-                RuleActionType::Delete => self.delete(path_str),
-                RuleActionType::Copy => self.copy(path_str),
-                RuleActionType::Link => self.link(path_str),
-                RuleActionType::Chmod => self.chmod(path_str),
-            };
+        // iterate over matched paths, collecting the planned-action record each method produces
+        let results: Vec<Result<PlannedAction>> = paths
+            .par_iter()
+            .map(|path| {
+                let path_str = path.as_str();
+                let fingerprint = file_fingerprint(path_str).ok();
 
-            match res {
-                Ok(_) => {
-                    if !*VERBOSE {
-                        // increment progress bar
-                        if let Some(ref progress_bar) = progress_bar {
-                            progress_bar.inc(1);
+                if let (Some(cache), Some(fingerprint)) = (&cache, &fingerprint) {
+                    if cache.already_processed(&cache_scope, fingerprint) {
+                        if *VERBOSE {
+                            info!("skipping already-processed file: {:?}", path_str);
                         }
+                        if !*VERBOSE {
+                            if let Some(ref progress_bar) = progress_bar {
+                                progress_bar.inc(1);
+                            }
+                        }
+                        return Ok(PlannedAction {
+                            action: self.action.planned_kind(),
+                            source: path_str.to_string(),
+                            destination: None,
+                            permissions: None,
+                            skipped: true,
+                        });
                     }
                 }
-                Err(e) => {
-                    if *VERBOSE {
-                        error!("error: {:?}", e);
-                    } else {
-                        // match progress bar
-                        match progress_bar {
-                            Some(ref progress_bar) => {
-                                progress_bar.println(format!("error: {:?}", e));
+
+                let res = match self.action {
+                    RuleActionType::Rename => self.rename(path_str),
+                    RuleActionType::Move => self.mv(path_str),
+                    RuleActionType::Delete => self.delete(path_str),
+                    RuleActionType::Copy => self.copy(path_str),
+                    RuleActionType::Link => self.link(path_str),
+                    RuleActionType::Chmod => self.chmod(path_str),
+                    RuleActionType::Archive => unreachable!("archive is handled before this loop"),
+                };
+
+                if let (Ok(_), Some(cache), Some(fingerprint), false) =
+                    (&res, &cache, &fingerprint, *DRY_RUN)
+                {
+                    cache.mark_processed(&cache_scope, fingerprint);
+                }
+
+                match &res {
+                    Ok(_) => {
+                        if !*VERBOSE {
+                            // increment progress bar
+                            if let Some(ref progress_bar) = progress_bar {
+                                progress_bar.inc(1);
                             }
-                            None => {
-                                error!("error: {:?}", e);
+                        }
+                    }
+                    Err(e) => {
+                        if *VERBOSE {
+                            error!("error: {:?}", e);
+                        } else {
+                            // match progress bar
+                            match progress_bar {
+                                Some(ref progress_bar) => {
+                                    progress_bar.println(format!("error: {:?}", e));
+                                }
+                                None => {
+                                    error!("error: {:?}", e);
+                                }
                             }
                         }
                     }
                 }
-            }
-        });
+
+                res
+            })
+            .collect();
 
         if !*VERBOSE {
             // match progress bar
@@ -152,21 +502,30 @@ impl RuleAction {
             }
         }
 
+        // in DRY_RUN, the planned actions collected above ARE the output: an
+        // auditable manifest instead of the scattered info! logging above
+        if *DRY_RUN {
+            let plan: Vec<&PlannedAction> =
+                results.iter().filter_map(|r| r.as_ref().ok()).collect();
+            let plan_json = serde_json::to_string_pretty(&plan)
+                .with_context(|| "Failed to serialize dry-run plan to JSON")?;
+            println!("{}", plan_json);
+        }
+
         Ok(())
     }
 
     /// rename action performs a file rename
-    fn rename(&self, path: &str) -> Result<()> {
-        // parse match_regex
-        let match_regex = match self.match_regex {
-            Some(ref regex) => regex,
-            None => {
-                return Err(anyhow!(
-                    "match_regex is required for rule action: {:?}",
-                    self
-                ))
-            }
-        };
+    fn rename(&self, path: &str) -> Result<PlannedAction> {
+        // resolve the match pattern, regardless of which syntax it was written in; unlike
+        // execute()'s match, rename needs capture groups from an actual regex, so
+        // match_patterns alone (with no match_regex/match_pattern) isn't enough here
+        let match_regex = self.compiled_match_regex()?.ok_or_else(|| {
+            anyhow!(
+                "match_regex or match_pattern/match_syntax is required for rule action {:?}",
+                self
+            )
+        })?;
 
         // parse rename_pattern
         let rename_pattern = match self.rename_pattern {
@@ -181,15 +540,17 @@ impl RuleAction {
 
         // build new path
         let new_filename =
-            generate_new_filename(self.watch_dir.as_str(), path, match_regex, rename_pattern)
+            generate_new_filename(self.watch_dir.as_str(), path, &match_regex, rename_pattern)
                 .with_context(|| format!("Failed to generate new filename for path: {}", path))?;
 
+        let skipped = path == new_filename;
+
         // rename file
         if *VERBOSE || *DRY_RUN {
             info!("renaming file: {:?} -> {:?}", path, new_filename);
         }
-        if !*DRY_RUN && path != new_filename {
-            fs::rename(path, new_filename).with_context(|| {
+        if !*DRY_RUN && !skipped {
+            fs::rename(path, &new_filename).with_context(|| {
                 format!(
                     "Failed to rename file: {:?} -> {:?}",
                     path,
@@ -198,11 +559,18 @@ impl RuleAction {
             })?;
         }
 
-        Ok(())
+        Ok(PlannedAction {
+            action: PlannedActionKind::Rename,
+            source: path.to_string(),
+            destination: Some(new_filename),
+            permissions: None,
+            skipped,
+        })
     }
 
-    /// mv action is a combination of copy and delete
-    fn mv(&self, path: &str) -> Result<()> {
+    /// mv action renames the file in place, falling back to copy+delete when
+    /// source and destination are on different filesystems
+    fn mv(&self, path: &str) -> Result<PlannedAction> {
         // parse destination_dir
         let destination_dir = match self.destination_dir {
             Some(ref dest) => dest.clone(),
@@ -214,32 +582,64 @@ impl RuleAction {
             }
         };
 
-        // parse filename
-        let filename = match path.split('/').last() {
-            Some(filename) => filename,
-            None => return Err(anyhow!("Failed to parse filename from path: {:?}", path)),
+        let new_path = resolve_destination(path, destination_dir.as_str())?;
+        // held through the rename/copy below too, not just the resolve_conflict call,
+        // since the race is between one thread's write and another thread's check
+        let _conflict_guard = lock_for_rename_conflict(self.on_conflict.as_ref());
+        let (new_path, skipped) = match resolve_conflict(new_path, self.on_conflict.as_ref()) {
+            Some(new_path) => {
+                let skipped = path == new_path;
+                (new_path, skipped)
+            }
+            None => {
+                if *VERBOSE || *DRY_RUN {
+                    info!("skipping move, destination already exists: {:?}", path);
+                }
+                return Ok(PlannedAction {
+                    action: PlannedActionKind::Move,
+                    source: path.to_string(),
+                    destination: None,
+                    permissions: None,
+                    skipped: true,
+                });
+            }
         };
 
-        // build mv path
-        let new_path = format!("{}/{}", destination_dir, filename,);
-
         // move file
         if *VERBOSE || *DRY_RUN {
             info!("moving file: {:?} -> {:?}", path, new_path);
         }
-        if !*DRY_RUN && path != new_path {
-            fs::copy(path, new_path.clone()).with_context(|| {
-                format!("Failed to copy file from {} to {}", path, new_path.clone())
-            })?;
+        if !*DRY_RUN && !skipped {
+            match fs::rename(path, new_path.as_str()) {
+                Ok(()) => {}
+                // EXDEV: source and destination are on different filesystems, rename can't work
+                Err(e) if e.raw_os_error() == Some(EXDEV) => {
+                    fs::copy(path, new_path.as_str()).with_context(|| {
+                        format!("Failed to copy file from {} to {}", path, new_path)
+                    })?;
 
-            fs::remove_file(path).with_context(|| format!("Failed to remove file {}", path))?;
+                    fs::remove_file(path)
+                        .with_context(|| format!("Failed to remove file {}", path))?;
+                }
+                Err(e) => {
+                    return Err(e).with_context(|| {
+                        format!("Failed to rename file: {:?} -> {:?}", path, new_path)
+                    })
+                }
+            }
         }
 
-        Ok(())
+        Ok(PlannedAction {
+            action: PlannedActionKind::Move,
+            source: path.to_string(),
+            destination: Some(new_path),
+            permissions: None,
+            skipped,
+        })
     }
 
     /// copy actions performs a simple copy
-    fn copy(&self, path: &str) -> Result<()> {
+    fn copy(&self, path: &str) -> Result<PlannedAction> {
         // parse destination_dir
         let destination_dir = match self.destination_dir {
             Some(ref dest) => dest.clone(),
@@ -251,15 +651,26 @@ impl RuleAction {
             }
         };
 
-        // parse filename
-        let filename = match path.split('/').last() {
-            Some(filename) => filename,
-            None => return Err(anyhow!("Failed to parse filename from path: {:?}", path)),
+        let new_path = resolve_destination(path, destination_dir.as_str())?;
+        // held through the copy below too, not just the resolve_conflict call,
+        // since the race is between one thread's write and another thread's check
+        let _conflict_guard = lock_for_rename_conflict(self.on_conflict.as_ref());
+        let new_path = match resolve_conflict(new_path, self.on_conflict.as_ref()) {
+            Some(new_path) => new_path,
+            None => {
+                if *VERBOSE || *DRY_RUN {
+                    info!("skipping copy, destination already exists: {:?}", path);
+                }
+                return Ok(PlannedAction {
+                    action: PlannedActionKind::Copy,
+                    source: path.to_string(),
+                    destination: None,
+                    permissions: None,
+                    skipped: true,
+                });
+            }
         };
 
-        // build copy path
-        let new_path = format!("{}/{}", destination_dir, filename,);
-
         // copy file
         if *VERBOSE || *DRY_RUN {
             info!("copying file: {:?} -> {:?}", path, new_path);
@@ -270,11 +681,17 @@ impl RuleAction {
             })?;
         }
 
-        Ok(())
+        Ok(PlannedAction {
+            action: PlannedActionKind::Copy,
+            source: path.to_string(),
+            destination: Some(new_path),
+            permissions: None,
+            skipped: false,
+        })
     }
 
     /// delete action performs a simple delete
-    fn delete(&self, path: &str) -> Result<()> {
+    fn delete(&self, path: &str) -> Result<PlannedAction> {
         // delete file
         if *VERBOSE || *DRY_RUN {
             info!("deleting file: {:?}", path);
@@ -283,11 +700,17 @@ impl RuleAction {
             fs::remove_file(path).with_context(|| format!("Failed to delete file {}", path))?;
         }
 
-        Ok(())
+        Ok(PlannedAction {
+            action: PlannedActionKind::Delete,
+            source: path.to_string(),
+            destination: None,
+            permissions: None,
+            skipped: false,
+        })
     }
 
     /// link action performs a simple link
-    fn link(&self, path: &str) -> Result<()> {
+    fn link(&self, path: &str) -> Result<PlannedAction> {
         // parse destination_dir
         let destination_dir = match self.destination_dir {
             Some(ref dest) => dest.clone(),
@@ -299,30 +722,68 @@ impl RuleAction {
             }
         };
 
-        // parse filename
-        let filename = match path.split('/').last() {
-            Some(filename) => filename,
-            None => return Err(anyhow!("Failed to parse filename from path: {:?}", path)),
+        let new_path = resolve_destination(path, destination_dir.as_str())?;
+        // held through the link below too, not just the resolve_conflict call,
+        // since the race is between one thread's write and another thread's check
+        let _conflict_guard = lock_for_rename_conflict(self.on_conflict.as_ref());
+        let new_path = match resolve_conflict(new_path, self.on_conflict.as_ref()) {
+            Some(new_path) => new_path,
+            None => {
+                if *VERBOSE || *DRY_RUN {
+                    info!("skipping link, destination already exists: {:?}", path);
+                }
+                return Ok(PlannedAction {
+                    action: PlannedActionKind::Link,
+                    source: path.to_string(),
+                    destination: None,
+                    permissions: None,
+                    skipped: true,
+                });
+            }
         };
 
-        // build link path
-        let new_path = format!("{}/{}", destination_dir, filename,);
-
         // link file
         if *VERBOSE || *DRY_RUN {
             info!("linking file: {:?} -> {:?}", path, new_path);
         }
         if !*DRY_RUN {
-            fs::hard_link(path, new_path.clone()).with_context(|| {
-                format!("Failed to link file from {} to {}", path, new_path.clone())
-            })?;
+            // hard_link/symlink never replace an existing path, so overwrite removes it first
+            if path != new_path
+                && matches!(self.on_conflict, None | Some(OnConflict::Overwrite))
+                && fs::symlink_metadata(&new_path).is_ok()
+            {
+                fs::remove_file(&new_path)
+                    .with_context(|| format!("Failed to remove existing file {}", new_path))?;
+            }
+            match self.link_type.as_deref() {
+                Some("symbolic") | Some("symlink") => {
+                    std::os::unix::fs::symlink(path, new_path.clone()).with_context(|| {
+                        format!(
+                            "Failed to symlink file from {} to {}",
+                            path,
+                            new_path.clone()
+                        )
+                    })?;
+                }
+                _ => {
+                    fs::hard_link(path, new_path.clone()).with_context(|| {
+                        format!("Failed to link file from {} to {}", path, new_path.clone())
+                    })?;
+                }
+            }
         }
 
-        Ok(())
+        Ok(PlannedAction {
+            action: PlannedActionKind::Link,
+            source: path.to_string(),
+            destination: Some(new_path),
+            permissions: None,
+            skipped: false,
+        })
     }
 
     /// chmod action performs a simple chmod
-    fn chmod(&self, path: &str) -> Result<()> {
+    fn chmod(&self, path: &str) -> Result<PlannedAction> {
         // parse mode
         let mode = match self.permissions {
             Some(ref mode) => mode,
@@ -338,10 +799,223 @@ impl RuleAction {
                 .with_context(|| format!("Failed to set permissions for file: {}", path))?;
         }
 
-        Ok(())
+        Ok(PlannedAction {
+            action: PlannedActionKind::Chmod,
+            source: path.to_string(),
+            destination: None,
+            permissions: Some(*mode),
+            skipped: false,
+        })
+    }
+
+    /// archive action zips every matched path into a single destination archive.
+    /// Unlike the other actions, it acts once on the whole match set rather than
+    /// per path - but it still produces a `PlannedAction` per file, so a
+    /// `DRY_RUN` archive gets the same auditable JSON manifest every other
+    /// action does instead of only logging through `info!`.
+    fn archive(&self, paths: &[String]) -> Result<Vec<PlannedAction>> {
+        let destination = match self.destination_dir {
+            Some(ref dest) => dest.clone(),
+            None => {
+                return Err(anyhow!(
+                    "destination_dir is required for rule action: {:?}",
+                    self
+                ))
+            }
+        };
+
+        if *VERBOSE || *DRY_RUN {
+            info!("archiving {} file(s) into {:?}", paths.len(), destination);
+        }
+
+        let progress_bar = if *VERBOSE {
+            None
+        } else {
+            Some(ProgressBar::new(u64::try_from(paths.len())?))
+        };
+
+        let mut planned = Vec::with_capacity(paths.len());
+
+        if *DRY_RUN {
+            for path in paths {
+                let entry_name = self.archive_entry_name(path)?;
+                if *VERBOSE {
+                    info!(
+                        "would add {:?} as {:?} to archive {:?}",
+                        path, entry_name, destination
+                    );
+                }
+                if let Some(ref progress_bar) = progress_bar {
+                    progress_bar.inc(1);
+                }
+                planned.push(PlannedAction {
+                    action: PlannedActionKind::Archive,
+                    source: path.clone(),
+                    destination: Some(entry_name),
+                    permissions: None,
+                    skipped: false,
+                });
+            }
+            if let Some(progress_bar) = progress_bar {
+                progress_bar.finish_and_clear();
+            }
+            return Ok(planned);
+        }
+
+        let file = fs::File::create(&destination)
+            .with_context(|| format!("Failed to create archive at {}", destination))?;
+        let mut zip = zip::ZipWriter::new(file);
+
+        let compression = match self.archive_compression.as_deref() {
+            Some("stored") => zip::CompressionMethod::Stored,
+            Some("bzip2") => zip::CompressionMethod::Bzip2,
+            _ => zip::CompressionMethod::Deflated,
+        };
+        let mut options = zip::write::FileOptions::default().compression_method(compression);
+        if let Some(level) = self.archive_level {
+            options = options.compression_level(Some(i32::try_from(level)?));
+        }
+
+        for path in paths {
+            let entry_name = self.archive_entry_name(path)?;
+
+            zip.start_file(entry_name.clone(), options)
+                .with_context(|| format!("Failed to start archive entry {:?}", entry_name))?;
+            let contents =
+                fs::read(path).with_context(|| format!("Failed to read file {}", path))?;
+            zip.write_all(&contents)
+                .with_context(|| format!("Failed to write archive entry {:?}", entry_name))?;
+
+            if let Some(ref progress_bar) = progress_bar {
+                progress_bar.inc(1);
+            }
+
+            planned.push(PlannedAction {
+                action: PlannedActionKind::Archive,
+                source: path.clone(),
+                destination: Some(entry_name),
+                permissions: None,
+                skipped: false,
+            });
+        }
+
+        zip.finish()
+            .with_context(|| format!("Failed to finalize archive {}", destination))?;
+        if let Some(progress_bar) = progress_bar {
+            progress_bar.finish_and_clear();
+        }
+
+        Ok(planned)
+    }
+
+    /// ``archive_entry_name`` is the `watch_dir`-relative path used as the zip entry
+    /// name, optionally run through `rename_pattern`'s capture expansion like `rename` does
+    fn archive_entry_name(&self, path: &str) -> Result<String> {
+        let trunc_path = str::replace(path, self.watch_dir.as_str(), "");
+        let trunc_path = trunc_path.trim_start_matches('/');
+
+        let rename_pattern = match self.rename_pattern {
+            Some(ref pattern) => pattern,
+            None => return Ok(trunc_path.to_string()),
+        };
+
+        let match_regex = self.compiled_match_regex()?.ok_or_else(|| {
+            anyhow!(
+                "match_regex or match_pattern/match_syntax is required for rule action {:?}",
+                self
+            )
+        })?;
+        let caps = match_regex
+            .captures(trunc_path)
+            .with_context(|| format!("Failed to parse captures for file: {:?}", path))?;
+
+        let mut entry_name = String::new();
+        caps.expand(rename_pattern, &mut entry_name);
+
+        Ok(entry_name.trim_start_matches('/').to_string())
     }
 }
 
+/// ``glob_to_regex`` translates a shell-style glob into an anchored regex
+/// string: `**/` becomes an optional any-depth directory prefix, `*` matches
+/// within a path segment, `?` matches a single character, `[...]` character
+/// classes pass through verbatim, and `{a,b,c}` becomes `(?:a|b|c)`.
+fn glob_to_regex(glob: &str) -> String {
+    let chars: Vec<char> = glob.chars().collect();
+    let mut out = String::from("^");
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') && chars.get(i + 2) == Some(&'/') => {
+                out.push_str("(?:.*/)?");
+                i += 3;
+            }
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                out.push_str(".*");
+                i += 2;
+            }
+            '*' => {
+                out.push_str("[^/]*");
+                i += 1;
+            }
+            '?' => {
+                out.push('.');
+                i += 1;
+            }
+            '[' => {
+                out.push('[');
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    let closed = chars[i] == ']';
+                    i += 1;
+                    if closed {
+                        break;
+                    }
+                }
+            }
+            '{' => {
+                let mut depth = 1;
+                let mut inner = String::new();
+                i += 1;
+                while i < chars.len() && depth > 0 {
+                    match chars[i] {
+                        '{' => depth += 1,
+                        '}' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                i += 1;
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    if depth > 0 {
+                        inner.push(chars[i]);
+                    }
+                    i += 1;
+                }
+                out.push_str("(?:");
+                out.push_str(&inner.split(',').collect::<Vec<_>>().join("|"));
+                out.push(')');
+            }
+            c @ ('.' | '+' | '(' | ')' | '|' | '^' | '$' | '\\') => {
+                out.push('\\');
+                out.push(c);
+                i += 1;
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out.push('$');
+    out
+}
+
 /// Rule represents a single rule for processing files
 #[derive(Debug, Deserialize)]
 pub struct Rule {
@@ -351,38 +1025,332 @@ pub struct Rule {
     pub actions: Vec<RuleAction>,
 }
 
-/// ``match_directory_listing`` matches a directory listing against a regex
+/// ``parse_interval`` parses a ``Rule::interval`` string into a ``Duration``:
+/// a bare number is seconds, and a trailing `s`/`m`/`h` suffix scales it into
+/// seconds/minutes/hours.
+pub fn parse_interval(interval: &str) -> Result<std::time::Duration> {
+    let (value, unit) = match interval.chars().last() {
+        Some(c) if c.is_ascii_alphabetic() => (&interval[..interval.len() - 1], c),
+        _ => (interval, 's'),
+    };
+
+    let value: u64 = value
+        .parse()
+        .with_context(|| format!("Failed to parse interval: {:?}", interval))?;
+
+    let seconds = match unit {
+        's' => value,
+        'm' => value * 60,
+        'h' => value * 60 * 60,
+        _ => return Err(anyhow!("Unsupported interval unit in {:?}", interval)),
+    };
+
+    Ok(std::time::Duration::from_secs(seconds))
+}
+
+/// ``Cache`` is a best-effort SQLite-backed ledger of which files a rule action has already
+/// processed, keyed by `RuleAction::cache_scope` plus a size+mtime fingerprint so a changed
+/// file is reprocessed. Failures to open or use the database degrade to "nothing cached"
+/// rather than failing the rule.
+struct Cache {
+    conn: Option<Mutex<rusqlite::Connection>>,
+}
+
+impl Cache {
+    /// ``default_path`` is where the ledger lives when a rule's `cache_path` is unset
+    fn default_path() -> String {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        format!("{}/.filewatch/cache.sqlite3", home)
+    }
+
+    /// ``open`` opens (and migrates) the ledger at `path`. Any failure is logged and
+    /// degrades to an always-empty cache.
+    fn open(path: &str) -> Cache {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                warn!("failed to create cache directory {:?}: {:?}", parent, e);
+            }
+        }
+
+        let opened = rusqlite::Connection::open(path).and_then(|conn| {
+            conn.execute(
+                "CREATE TABLE IF NOT EXISTS processed (
+                    scope TEXT NOT NULL,
+                    fingerprint TEXT NOT NULL,
+                    PRIMARY KEY (scope, fingerprint)
+                )",
+                [],
+            )?;
+            Ok(conn)
+        });
+
+        match opened {
+            Ok(conn) => Cache {
+                conn: Some(Mutex::new(conn)),
+            },
+            Err(e) => {
+                warn!("failed to open cache at {:?}, disabling it: {:?}", path, e);
+                Cache { conn: None }
+            }
+        }
+    }
+
+    /// ``already_processed`` reports whether `scope`/`fingerprint` is recorded in the ledger
+    fn already_processed(&self, scope: &str, fingerprint: &str) -> bool {
+        let Some(conn) = &self.conn else {
+            return false;
+        };
+        let Ok(conn) = conn.lock() else {
+            return false;
+        };
+
+        conn.query_row(
+            "SELECT 1 FROM processed WHERE scope = ?1 AND fingerprint = ?2",
+            rusqlite::params![scope, fingerprint],
+            |_| Ok(()),
+        )
+        .is_ok()
+    }
+
+    /// ``mark_processed`` records `scope`/`fingerprint` as done; failures are logged and ignored
+    fn mark_processed(&self, scope: &str, fingerprint: &str) {
+        let Some(conn) = &self.conn else {
+            return;
+        };
+        let Ok(conn) = conn.lock() else {
+            return;
+        };
+
+        if let Err(e) = conn.execute(
+            "INSERT OR REPLACE INTO processed (scope, fingerprint) VALUES (?1, ?2)",
+            rusqlite::params![scope, fingerprint],
+        ) {
+            warn!(
+                "failed to record {:?}/{:?} in cache: {:?}",
+                scope, fingerprint, e
+            );
+        }
+    }
+}
+
+/// ``file_fingerprint`` is a cheap change-detection key for `path`: its size and mtime
+fn file_fingerprint(path: &str) -> Result<String> {
+    let metadata = fs::metadata(path).with_context(|| format!("Failed to stat file: {}", path))?;
+    let modified = metadata
+        .modified()
+        .with_context(|| format!("Failed to read mtime for file: {}", path))?
+        .duration_since(std::time::UNIX_EPOCH)
+        .with_context(|| format!("Failed to compute mtime for file: {}", path))?
+        .as_secs();
+
+    Ok(format!("{}:{}", metadata.len(), modified))
+}
+
+/// ``reset_cache`` deletes the ledger at `path` (or `Cache::default_path()`), so every
+/// file is treated as unprocessed on the next run
+pub fn reset_cache(path: Option<&str>) -> Result<()> {
+    let path = path.map(str::to_string).unwrap_or_else(Cache::default_path);
+
+    match fs::remove_file(&path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e).with_context(|| format!("Failed to reset cache at {:?}", path)),
+    }
+}
+
+/// ``compile_pattern`` compiles a single pattern string into a `regex::Regex`
+/// according to `syntax` (raw regex when `None` or `Regex`, glob/path translation otherwise).
+fn compile_pattern(syntax: Option<&MatchSyntax>, pattern: &str) -> Result<::regex::Regex> {
+    match syntax {
+        None | Some(MatchSyntax::Regex) => ::regex::Regex::new(pattern),
+        // glob matches at any depth unless the pattern already opts into that itself,
+        // which is exactly what prepending an (unanchored) "**/" achieves
+        Some(MatchSyntax::Glob) if !pattern.starts_with("**/") => {
+            ::regex::Regex::new(&glob_to_regex(&format!("**/{}", pattern)))
+        }
+        Some(MatchSyntax::Glob) => ::regex::Regex::new(&glob_to_regex(pattern)),
+        // the truncated path starts with '/' unless watch_dir ends in one, so the
+        // root-anchored syntaxes accept an optional leading '/', as RootFilesIn does
+        Some(MatchSyntax::RootGlob) => ::regex::Regex::new(&format!(
+            "^/?{}",
+            &glob_to_regex(pattern.trim_start_matches('/'))[1..]
+        )),
+        Some(MatchSyntax::Path) => ::regex::Regex::new(&format!(
+            "^/?{}(?:/.*)?$",
+            ::regex::escape(pattern.trim_matches('/'))
+        )),
+        Some(MatchSyntax::RootFilesIn) => ::regex::Regex::new(&format!(
+            "^/?{}/[^/]+$",
+            ::regex::escape(pattern.trim_matches('/'))
+        )),
+    }
+    .with_context(|| format!("Failed to compile pattern {:?}", pattern))
+}
+
+/// ``is_literal`` reports whether `pattern` has no regex metacharacters, so it
+/// can be matched with a plain equality check instead of through `regex_set`.
+fn is_literal(pattern: &str) -> bool {
+    !pattern.contains(|c: char| r"\.+*?()|[]{}^$".contains(c))
+}
+
+/// ``PatternSet`` compiles a list of patterns into a single matcher: literal
+/// patterns are checked with a plain equality fast path, and everything else is
+/// tested in one pass through a `regex::RegexSet`.
+struct PatternSet {
+    literals: Vec<String>,
+    regex_set: ::regex::RegexSet,
+}
+
+impl PatternSet {
+    fn new(syntax: Option<&MatchSyntax>, patterns: &[String]) -> Result<PatternSet> {
+        let mut literals = Vec::new();
+        let mut regexes = Vec::new();
+
+        for pattern in patterns {
+            if syntax.is_none() && is_literal(pattern) {
+                literals.push(pattern.clone());
+            } else {
+                regexes.push(compile_pattern(syntax, pattern)?.as_str().to_string());
+            }
+        }
+
+        let regex_set = ::regex::RegexSet::new(&regexes)
+            .with_context(|| format!("Failed to compile pattern set: {:?}", regexes))?;
+
+        Ok(PatternSet {
+            literals,
+            regex_set,
+        })
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        self.literals.iter().any(|literal| literal == text) || self.regex_set.is_match(text)
+    }
+}
+
+/// ``BadFileType`` classifies a walked entry that isn't a regular file and so can never match
+#[derive(Debug)]
+pub enum BadFileType {
+    CharacterDevice,
+    BlockDevice,
+    Fifo,
+    Socket,
+}
+
+/// ``BadMatch`` records why a walked entry was skipped instead of silently dropped
+#[derive(Debug)]
+pub enum BadMatch {
+    /// the OS returned an error reading this entry (e.g. a permission error); carries `raw_os_error()`
+    OsError(i32),
+    /// the entry exists but isn't a type `match_directory_listing` will ever match
+    BadType(BadFileType),
+}
+
+/// ``MatchResult`` is everything `match_directory_listing` learned about a walk:
+/// the files that matched, plus every entry it had to skip and why.
+pub struct MatchResult {
+    pub paths: Vec<String>,
+    pub bad_matches: Vec<BadMatch>,
+}
+
+/// ``match_directory_listing`` matches a directory listing against `is_match`,
+/// pruning any path - and, for directories, everything beneath it - that
+/// matches one of `exclude_regexes` before it's ever visited. Entries that
+/// can't be read, or that aren't a type this function can match, are
+/// classified into `MatchResult::bad_matches` instead of silently dropped.
 pub fn match_directory_listing(
     path: &str,
-    match_regex: &Regex,
-) -> Result<Vec<String>, anyhow::Error> {
+    is_match: impl Fn(&str) -> bool,
+    exclude_regexes: &[::regex::Regex],
+) -> Result<MatchResult, anyhow::Error> {
     let mut paths = Vec::new();
-    for e in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-        // parse metadata
-        let metadata = e.metadata().with_context(|| {
+    let mut bad_matches = Vec::new();
+
+    let walker = WalkDir::new(path).into_iter().filter_entry(|e| {
+        let filepath = match e.path().to_str() {
+            Some(filepath) => filepath,
+            None => return true,
+        };
+        let trunc_path = str::replace(filepath, path, "");
+        !exclude_regexes
+            .iter()
+            .any(|re| re.is_match(trunc_path.as_str()))
+    });
+
+    for entry in walker {
+        let e = match entry {
+            Ok(e) => e,
+            Err(err) => {
+                bad_matches.push(BadMatch::OsError(
+                    err.io_error()
+                        .and_then(|io_err| io_err.raw_os_error())
+                        .unwrap_or(-1),
+                ));
+                continue;
+            }
+        };
+
+        // root entry itself is never a candidate match
+        if e.path() == std::path::Path::new(path) {
+            continue;
+        }
+
+        // directories aren't "unreadable or unsupported" - WalkDir has to descend
+        // into them to do its job, so they're skipped without counting as a bad match
+        if e.file_type().is_dir() {
+            continue;
+        }
+
+        // fs::metadata() follows symlinks (unlike file_type() and walkdir's own
+        // metadata(), which report the link itself), so a symlink to an ordinary
+        // regular file is treated exactly like that file instead of being excluded
+        // as an unsupported type
+        let metadata = match fs::metadata(e.path()) {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                bad_matches.push(BadMatch::OsError(err.raw_os_error().unwrap_or(-1)));
+                continue;
+            }
+        };
+
+        // a symlink to a directory, resolved by the metadata() call above
+        if metadata.is_dir() {
+            continue;
+        }
+
+        let file_type = metadata.file_type();
+        let bad_type = if file_type.is_char_device() {
+            Some(BadFileType::CharacterDevice)
+        } else if file_type.is_block_device() {
+            Some(BadFileType::BlockDevice)
+        } else if file_type.is_fifo() {
+            Some(BadFileType::Fifo)
+        } else if file_type.is_socket() {
+            Some(BadFileType::Socket)
+        } else {
+            None
+        };
+
+        if let Some(bad_type) = bad_type {
+            bad_matches.push(BadMatch::BadType(bad_type));
+            continue;
+        }
+
+        // only a regular file is left at this point
+        let filepath = e.path().to_str().with_context(|| {
             format!(
-                "Failed to parse metadata for file: {:?}",
+                "Failed to parse filepath for file: {:?}",
                 e.path().to_string_lossy()
             )
         })?;
 
-        // if path is a file, check if it matches the regex
-        if metadata.is_file() {
-            let filepath = e.path().to_str().with_context(|| {
-                format!(
-                    "Failed to parse filepath for file: {:?}",
-                    e.path().to_string_lossy()
-                )
-            })?;
-
-            let trunc_path = str::replace(filepath, path, "");
-            if match_regex.is_match(trunc_path.as_str()) {
-                paths.push(filepath.to_owned());
-            }
+        let trunc_path = str::replace(filepath, path, "");
+        if is_match(trunc_path.as_str()) {
+            paths.push(filepath.to_owned());
         }
     }
 
-    Ok(paths)
+    Ok(MatchResult { paths, bad_matches })
 }
 
 /// ``generate_new_filename`` generates a new filename based on the regex capture groups
@@ -404,3 +1372,101 @@ pub fn generate_new_filename(
 
     Ok(new_name)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a minimal `RuleAction` with every field at its "unset" default except `action`
+    /// and `destination_dir`, for tests that only exercise one field at a time
+    fn bare_action() -> RuleAction {
+        RuleAction {
+            action: RuleActionType::Copy,
+            watch_dir: String::new(),
+            match_regex: None,
+            match_syntax: None,
+            match_pattern: None,
+            match_patterns: None,
+            exclude_patterns: None,
+            rename_pattern: None,
+            destination_dir: None,
+            on_conflict: None,
+            cache_path: None,
+            permissions: None,
+            archive_compression: None,
+            archive_level: None,
+            link_type: None,
+        }
+    }
+
+    #[test]
+    fn compiled_match_regex_allows_match_patterns_alone() {
+        let mut rule = bare_action();
+        rule.match_patterns = Some(vec!["*.log".to_string()]);
+
+        assert!(rule.compiled_match_regex().unwrap().is_none());
+    }
+
+    #[test]
+    fn compiled_match_regex_errors_with_no_pattern_at_all() {
+        let rule = bare_action();
+
+        assert!(rule.compiled_match_regex().is_err());
+    }
+
+    #[test]
+    fn rename_conflict_resolution_is_race_free_under_concurrent_copies() {
+        let test_dir =
+            std::env::temp_dir().join(format!("filewatch-test-{}-{}", std::process::id(), line!()));
+        fs::create_dir_all(&test_dir).expect("failed to create test dir");
+
+        let src = test_dir.join("source.txt");
+        fs::write(&src, b"hello").expect("failed to write source file");
+
+        let mut rule = bare_action();
+        rule.action = RuleActionType::Copy;
+        rule.destination_dir = Some(test_dir.to_string_lossy().into_owned());
+        rule.on_conflict = Some(OnConflict::Rename);
+
+        let src_str = src.to_string_lossy().into_owned();
+
+        // several threads race to copy the same source into the same destination_dir
+        // under OnConflict::Rename; without RENAME_CONFLICT_LOCK, two of them can resolve
+        // to the same "(n)" candidate and one write silently clobbers the other
+        let destinations: Vec<String> = std::thread::scope(|scope| {
+            (0..8)
+                .map(|_| {
+                    let rule = &rule;
+                    let src_str = src_str.as_str();
+                    scope.spawn(move || {
+                        rule.copy(src_str)
+                            .expect("copy should succeed")
+                            .destination
+                            .expect("copy should always produce a destination")
+                    })
+                })
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().expect("copy thread panicked"))
+                .collect()
+        });
+
+        let mut unique = destinations.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(
+            unique.len(),
+            destinations.len(),
+            "every concurrent rename-on-conflict copy must land on a distinct path: {:?}",
+            destinations
+        );
+        for destination in &destinations {
+            assert_eq!(
+                fs::read(destination).expect("every resolved destination should exist"),
+                b"hello"
+            );
+        }
+
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+}
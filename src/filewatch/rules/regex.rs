@@ -9,6 +9,12 @@ impl core::ops::Deref for Regex {
     }
 }
 
+impl From<regex::Regex> for Regex {
+    fn from(re: regex::Regex) -> Regex {
+        Regex(re)
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for Regex {
     fn deserialize<D>(de: D) -> Result<Self, D::Error>
     where
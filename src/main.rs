@@ -4,7 +4,7 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use console::{style, Emoji};
 use lazy_static::lazy_static;
-use log::info;
+use log::{error, info};
 use simple_logger::SimpleLogger;
 use std::collections::HashMap;
 
@@ -20,6 +20,13 @@ struct Args {
     /// dry run flag
     #[arg(short, long)]
     dry_run: bool,
+    /// watch flag: keep running each rule on its own interval instead of a single pass
+    #[arg(short, long)]
+    watch: bool,
+    /// reset_cache flag: clear the default processed-file ledger before running (a ledger at a
+    /// rule's own cache_path is left as it is)
+    #[arg(long)]
+    reset_cache: bool,
 }
 
 lazy_static! {
@@ -29,6 +36,8 @@ lazy_static! {
     static ref VERBOSE: bool = CLI.verbose;
     // read dry_run from cli args
     static ref DRY_RUN: bool = CLI.dry_run;
+    // read watch from cli args
+    static ref WATCH: bool = CLI.watch;
 
     // RuleActionType to String HashMap
     static ref RULE_ACTION_TYPE_MAP: HashMap<filewatch::rules::RuleActionType, &'static str> = {
@@ -38,6 +47,7 @@ lazy_static! {
         m.insert(filewatch::rules::RuleActionType::Delete, "delete");
         m.insert(filewatch::rules::RuleActionType::Copy, "copy");
         m.insert(filewatch::rules::RuleActionType::Link, "link");
+        m.insert(filewatch::rules::RuleActionType::Archive, "archive");
         m
     };
     static ref RULE_ACTION_TYPE_EMOJI_MAP: HashMap<filewatch::rules::RuleActionType, &'static str> = {
@@ -47,6 +57,7 @@ lazy_static! {
         m.insert(filewatch::rules::RuleActionType::Delete, "\u{1f5d1}\u{fe0f}  ");
         m.insert(filewatch::rules::RuleActionType::Copy, "\u{1f4cb} ");
         m.insert(filewatch::rules::RuleActionType::Link, "\u{1f517} ");
+        m.insert(filewatch::rules::RuleActionType::Archive, "\u{1f4e6} ");
         m
     };
 }
@@ -58,28 +69,57 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             .with_context(|| "Failed to initialize logger")?;
     }
 
+    if CLI.reset_cache {
+        filewatch::rules::reset_cache(None)?;
+    }
+
     // only run if file is present
     if std::path::Path::new(&CLI.file).exists() {
         let f = std::fs::File::open(&CLI.file)?;
         let rules: filewatch::Rules = serde_yaml::from_reader(f)?;
 
-        for (rule_name, rule) in &rules {
-            if *VERBOSE || *DRY_RUN {
-                info!("executing rule: {:?}", rule_name);
-            } else {
-                println!(
-                    "{} {}",
-                    style("executing rule").bold().dim(),
-                    style(rule_name).bold()
-                );
+        if CLI.watch {
+            // run each rule forever, on its own interval, until the process is killed
+            std::thread::scope(|scope| {
+                for (rule_name, rule) in &rules {
+                    scope.spawn(move || loop {
+                        announce_rule(rule_name);
+                        let _result = execute_rule(rule);
+
+                        match filewatch::rules::parse_interval(&rule.interval) {
+                            Ok(interval) => std::thread::sleep(interval),
+                            Err(e) => {
+                                error!("invalid interval for rule {:?}: {:?}", rule_name, e);
+                                break;
+                            }
+                        }
+                    });
+                }
+            });
+        } else {
+            for (rule_name, rule) in &rules {
+                announce_rule(rule_name);
+                let _result = execute_rule(rule);
             }
-            let _result = execute_rule(rule);
         }
     }
 
     Ok(())
 }
 
+/// ``announce_rule`` logs or prints that `rule_name` is about to run, depending on verbosity
+fn announce_rule(rule_name: &str) {
+    if *VERBOSE || *DRY_RUN {
+        info!("executing rule: {:?}", rule_name);
+    } else {
+        println!(
+            "{} {}",
+            style("executing rule").bold().dim(),
+            style(rule_name).bold()
+        );
+    }
+}
+
 /// ``execute_rule`` is a function that takes a ``Rule`` struct and executes it
 fn execute_rule(rule: &filewatch::rules::Rule) -> Result<()> {
     for (i, action) in rule.actions.iter().enumerate() {